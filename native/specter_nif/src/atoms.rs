@@ -0,0 +1,51 @@
+rustler::atoms! {
+    atom ok;
+    atom error;
+
+    // connection / ICE / signaling states
+    atom new;
+    atom checking;
+    atom connecting;
+    atom connected;
+    atom completed;
+    atom disconnected;
+    atom failed;
+    atom closed;
+    atom gathering;
+    atom complete;
+    atom stable;
+    atom have_local_offer;
+    atom have_remote_offer;
+    atom have_local_pranswer;
+    atom have_remote_pranswer;
+    atom unspecified;
+
+    // track kind, see events::register
+    atom audio;
+    atom video;
+
+    // SDP negotiation
+    atom offer;
+    atom answer;
+    atom sdp;
+    atom r#type;
+
+    // errors
+    atom lock_failure;
+    atom not_found;
+    atom invalid_config;
+
+    // get_stats report decoding, see state::json_to_term
+    atom nil;
+
+    // event message tags, see state::register_callbacks
+    atom ice_candidate;
+    atom ice_connection_state_change;
+    atom track;
+    atom data_channel;
+    atom negotiation_needed;
+    atom data_channel_open;
+    atom data_channel_close;
+    atom data_channel_message;
+    atom data_channel_buffered_amount_low;
+}
@@ -0,0 +1,36 @@
+use rustler::NifStruct;
+use webrtc::ice_transport::ice_server::RTCIceServer;
+use webrtc::peer_connection::configuration::RTCConfiguration;
+
+/// Elixir-facing description of how a [`crate::state::Ref`] should configure
+/// its underlying `RTCPeerConnection`.
+///
+/// This mirrors `%Specter.Config{}` on the Elixir side: the `#[derive(NifStruct)]`
+/// gives us both directions for free, decoding whatever `%Specter.Config{}`
+/// Elixir passes to `state::init/1` and encoding this same shape back out of
+/// `state::get_config/1`, rather than round-tripping through an opaque term.
+#[derive(Debug, Clone, NifStruct)]
+#[module = "Elixir.Specter.Config"]
+pub struct Config {
+    pub ice_servers: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            ice_servers: vec!["stun:stun.l.google.com:19302".to_string()],
+        }
+    }
+}
+
+impl Config {
+    pub fn into_rtc_configuration(self) -> RTCConfiguration {
+        RTCConfiguration {
+            ice_servers: vec![RTCIceServer {
+                urls: self.ice_servers,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use rustler::resource::WeakResourceArc;
+use rustler::types::binary::OwnedBinary;
+use rustler::{Binary, Encoder, Env, LocalPid, NifResult, NifStruct, OwnedEnv, ResourceArc};
+use tokio::runtime::Runtime;
+use webrtc::data_channel::data_channel_init::RTCDataChannelInit;
+use webrtc::data_channel::data_channel_message::DataChannelMessage;
+use webrtc::data_channel::RTCDataChannel;
+
+use crate::atoms;
+use crate::state::Ref;
+
+pub type DataChannelId = u64;
+
+/// Elixir-facing knobs for `create_data_channel/3`, mirroring
+/// `%Specter.DataChannelOptions{}`. Every field is optional and falls back
+/// to `webrtc-rs`'s own default (ordered, reliable, unnegotiated) when left
+/// `nil`, the same `NifStruct`-round-trips-for-free approach used by
+/// [`crate::config::Config`].
+#[derive(Debug, Clone, Default, NifStruct)]
+#[module = "Elixir.Specter.DataChannelOptions"]
+pub struct DataChannelOptions {
+    pub ordered: Option<bool>,
+    pub max_packet_life_time: Option<u16>,
+    pub max_retransmits: Option<u16>,
+    pub protocol: Option<String>,
+    pub negotiated: Option<bool>,
+}
+
+impl From<DataChannelOptions> for RTCDataChannelInit {
+    fn from(opts: DataChannelOptions) -> Self {
+        RTCDataChannelInit {
+            ordered: opts.ordered,
+            max_packet_life_time: opts.max_packet_life_time,
+            max_retransmits: opts.max_retransmits,
+            protocol: opts.protocol,
+            negotiated: opts.negotiated,
+            ..Default::default()
+        }
+    }
+}
+
+/// Channels created before or after negotiation are addressed from Elixir by
+/// a stable id we hand out ourselves, rather than the SCTP stream id
+/// `webrtc-rs` only assigns once the channel is actually open.
+#[derive(Default)]
+pub struct DataChannels {
+    next_id: DataChannelId,
+    channels: HashMap<DataChannelId, Arc<RTCDataChannel>>,
+}
+
+impl DataChannels {
+    fn insert(&mut self, channel: Arc<RTCDataChannel>) -> DataChannelId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.channels.insert(id, channel);
+        id
+    }
+
+    fn get(&self, id: DataChannelId) -> Option<Arc<RTCDataChannel>> {
+        self.channels.get(&id).cloned()
+    }
+
+    fn remove(&mut self, id: DataChannelId) {
+        self.channels.remove(&id);
+    }
+}
+
+/// Store `channel` under a fresh id in `resource`'s channel table and wire
+/// up its open/close/message/buffered-amount-low callbacks to forward to
+/// `pid`, using the same per-message `OwnedEnv` pattern as
+/// [`crate::events::register`]. Used both when Elixir initiates a channel
+/// via `create_data_channel` and when the remote peer opens one, delivered
+/// through `on_data_channel` in [`crate::events`]. When `pid` is `None`, the
+/// channel is only stored and no callbacks are wired up.
+///
+/// Each callback captures a `Weak` handle rather than `resource` itself:
+/// `resource.0.peer_connection` owns `channel`, so a strong `ResourceArc`
+/// captured here would form `Ref -> channel -> closure -> Ref`, a cycle the
+/// BEAM's refcounting GC can never break. A strong `ResourceArc` is
+/// materialized transiently, only for the duration of a single callback
+/// invocation, by `upgrade()`-ing the weak handle.
+pub fn register(resource: ResourceArc<Ref>, pid: Option<LocalPid>, channel: Arc<RTCDataChannel>) -> DataChannelId {
+    let id = {
+        let mut state = resource.0.lock().expect("specter: state lock poisoned");
+        state.data_channels.insert(channel.clone())
+    };
+
+    let Some(pid) = pid else {
+        return id;
+    };
+
+    let weak = resource.downgrade();
+
+    {
+        let weak = weak.clone();
+        channel.on_open(Box::new(move || {
+            let weak = weak.clone();
+            Box::pin(async move {
+                let Some(resource) = weak.upgrade() else {
+                    return;
+                };
+                send(pid, move |env| (atoms::data_channel_open(), resource, id).encode(env));
+            })
+        }));
+    }
+
+    {
+        let weak = weak.clone();
+        channel.on_close(Box::new(move || {
+            let weak = weak.clone();
+            Box::pin(async move {
+                let Some(resource) = weak.upgrade() else {
+                    return;
+                };
+                send(pid, move |env| (atoms::data_channel_close(), resource, id).encode(env));
+            })
+        }));
+    }
+
+    {
+        let weak = weak.clone();
+        channel.on_buffered_amount_low(Box::new(move || {
+            let weak = weak.clone();
+            Box::pin(async move {
+                let Some(resource) = weak.upgrade() else {
+                    return;
+                };
+                send(pid, move |env| {
+                    (atoms::data_channel_buffered_amount_low(), resource, id).encode(env)
+                });
+            })
+        }));
+    }
+
+    {
+        let weak = weak.clone();
+        channel.on_message(Box::new(move |message: DataChannelMessage| {
+            let weak = weak.clone();
+            let payload = message.data.to_vec();
+            Box::pin(async move {
+                let Some(resource) = weak.upgrade() else {
+                    return;
+                };
+                send(pid, move |env| {
+                    // `Vec<u8>` encodes as an Erlang list of integers, which would
+                    // turn every inbound message into an element-per-byte list.
+                    // Copy into an owned binary so Elixir sees a binary, same as
+                    // `data_channel_send` accepts on the way out.
+                    let mut binary = OwnedBinary::new(payload.len())
+                        .expect("specter: allocation failure encoding data channel payload");
+                    binary.as_mut_slice().copy_from_slice(&payload);
+                    let payload = binary.release(env);
+
+                    (atoms::data_channel_message(), resource, id, payload).encode(env)
+                });
+            })
+        }));
+    }
+
+    id
+}
+
+fn send<F>(pid: LocalPid, closure: F)
+where
+    F: for<'a> FnOnce(Env<'a>) -> rustler::Term<'a> + 'static,
+{
+    let mut owned_env = OwnedEnv::new();
+    let _ = owned_env.send_and_clear(&pid, closure);
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn create_data_channel(
+    resource: ResourceArc<Ref>,
+    label: String,
+    options: DataChannelOptions,
+) -> NifResult<DataChannelId> {
+    let (pc, runtime, pid) = {
+        let state = resource.0.lock().expect("specter: state lock poisoned");
+        let pc = state
+            .peer_connection
+            .clone()
+            .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))?;
+        (pc, state.runtime.clone(), state.pid)
+    };
+
+    let init = Some(options.into());
+    let channel = runtime
+        .block_on(async move { pc.create_data_channel(&label, init).await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    Ok(register(resource, pid, channel))
+}
+
+/// Send `data` as a binary message. See [`data_channel_send_text`] for the
+/// text-typed counterpart; inbound messages of either kind are delivered to
+/// Elixir as the same `{:data_channel_message, ref, id, binary}` shape,
+/// since an Elixir binary already represents UTF-8 text just fine.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn data_channel_send(
+    resource: ResourceArc<Ref>,
+    channel_id: DataChannelId,
+    data: Binary,
+) -> NifResult<rustler::Atom> {
+    let (channel, runtime) = channel_and_runtime(&resource, channel_id)?;
+    let payload = Bytes::copy_from_slice(data.as_slice());
+
+    runtime
+        .block_on(async move { channel.send(&payload).await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    Ok(atoms::ok())
+}
+
+/// Send `text` as a text-typed (as opposed to binary-typed) data channel
+/// message, per the text/binary distinction WebRTC data channels make
+/// on the wire. See [`data_channel_send`] for binary payloads.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn data_channel_send_text(
+    resource: ResourceArc<Ref>,
+    channel_id: DataChannelId,
+    text: String,
+) -> NifResult<rustler::Atom> {
+    let (channel, runtime) = channel_and_runtime(&resource, channel_id)?;
+
+    runtime
+        .block_on(async move { channel.send_text(text).await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn data_channel_close(resource: ResourceArc<Ref>, channel_id: DataChannelId) -> NifResult<rustler::Atom> {
+    let (channel, runtime) = channel_and_runtime(&resource, channel_id)?;
+
+    runtime
+        .block_on(async move { channel.close().await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    let mut state = resource.0.lock().expect("specter: state lock poisoned");
+    state.data_channels.remove(channel_id);
+
+    Ok(atoms::ok())
+}
+
+fn channel_and_runtime(
+    resource: &ResourceArc<Ref>,
+    channel_id: DataChannelId,
+) -> NifResult<(Arc<RTCDataChannel>, Arc<Runtime>)> {
+    let state = resource.0.lock().expect("specter: state lock poisoned");
+    let channel = state
+        .data_channels
+        .get(channel_id)
+        .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))?;
+
+    Ok((channel, state.runtime.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{negotiate, new_peer_connection};
+    use std::sync::Mutex as StdMutex;
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+    use tokio::time::timeout;
+
+    #[tokio::test]
+    async fn data_channel_send_and_receive_round_trip() {
+        let offerer = new_peer_connection().await;
+        let answerer = new_peer_connection().await;
+
+        let (remote_channel_tx, remote_channel_rx) = oneshot::channel();
+        let remote_channel_tx = StdMutex::new(Some(remote_channel_tx));
+        answerer.on_data_channel(Box::new(move |remote_channel| {
+            if let Some(tx) = remote_channel_tx.lock().expect("lock poisoned").take() {
+                let _ = tx.send(remote_channel);
+            }
+            Box::pin(async {})
+        }));
+
+        let options = DataChannelOptions {
+            ordered: Some(true),
+            ..Default::default()
+        };
+        let local_channel = offerer
+            .create_data_channel("chat", Some(options.into()))
+            .await
+            .expect("create_data_channel");
+
+        negotiate(&offerer, &answerer).await;
+
+        let remote_channel = timeout(Duration::from_secs(5), remote_channel_rx)
+            .await
+            .expect("remote data channel did not arrive in time")
+            .expect("remote_channel_tx dropped");
+
+        let (message_tx, message_rx) = oneshot::channel();
+        let message_tx = StdMutex::new(Some(message_tx));
+        remote_channel.on_message(Box::new(move |message: DataChannelMessage| {
+            if let Some(tx) = message_tx.lock().expect("lock poisoned").take() {
+                let _ = tx.send(message.data.to_vec());
+            }
+            Box::pin(async {})
+        }));
+
+        let (open_tx, open_rx) = oneshot::channel();
+        let open_tx = StdMutex::new(Some(open_tx));
+        local_channel.on_open(Box::new(move || {
+            if let Some(tx) = open_tx.lock().expect("lock poisoned").take() {
+                let _ = tx.send(());
+            }
+            Box::pin(async {})
+        }));
+
+        timeout(Duration::from_secs(5), open_rx)
+            .await
+            .expect("data channel did not open in time")
+            .expect("open_tx dropped");
+
+        local_channel
+            .send(&Bytes::from_static(b"hello"))
+            .await
+            .expect("send");
+
+        let received = timeout(Duration::from_secs(5), message_rx)
+            .await
+            .expect("message not received in time")
+            .expect("message_tx dropped");
+
+        assert_eq!(received, b"hello");
+
+        let _ = offerer.close().await;
+        let _ = answerer.close().await;
+    }
+}
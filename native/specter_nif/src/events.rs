@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use rustler::{Encoder, Env, LocalPid, OwnedEnv, ResourceArc};
+use webrtc::peer_connection::RTCPeerConnection;
+use webrtc::rtp_transceiver::rtp_codec::RTPCodecType;
+
+use crate::atoms;
+use crate::data_channel;
+use crate::state::{ice_connection_state_atom, Ref};
+
+/// Wire up the `webrtc-rs` event callbacks on `pc` so that every
+/// asynchronous event is forwarded to `pid` as a tagged message, e.g.
+/// `{:ice_candidate, ref, candidate_json}`.
+///
+/// These callbacks fire on tokio worker threads that never hold the NIF's
+/// `Env`, so each one builds a fresh [`OwnedEnv`] per message instead of
+/// trying to reuse the caller's `Env`. If `pid` is no longer alive,
+/// `send_and_clear` returns an error, which we drop: the owning process is
+/// gone, so there is nobody left to deliver the event to.
+///
+/// Each callback captures a `Weak` handle rather than `resource` itself:
+/// `resource.0.peer_connection` is `pc`, so a strong `ResourceArc` captured
+/// here would form `Ref -> pc -> closure -> Ref`, a cycle the BEAM's
+/// refcounting GC can never break. A strong `ResourceArc` is materialized
+/// transiently, only for the duration of a single callback invocation, by
+/// `upgrade()`-ing the weak handle; see [`data_channel::register`] for the
+/// same pattern on data channel callbacks.
+pub fn register(resource: ResourceArc<Ref>, pc: &Arc<RTCPeerConnection>, pid: LocalPid) {
+    let weak = resource.downgrade();
+
+    {
+        let weak = weak.clone();
+        pc.on_ice_candidate(Box::new(move |candidate| {
+            let weak = weak.clone();
+            Box::pin(async move {
+                let Some(resource) = weak.upgrade() else {
+                    return;
+                };
+                let Some(candidate) = candidate else {
+                    return;
+                };
+                let Ok(init) = candidate.to_json() else {
+                    return;
+                };
+                let Ok(json) = serde_json::to_string(&init) else {
+                    return;
+                };
+
+                send(pid, move |env| {
+                    (atoms::ice_candidate(), resource, json).encode(env)
+                });
+            })
+        }));
+    }
+
+    {
+        let weak = weak.clone();
+        pc.on_ice_connection_state_change(Box::new(move |state| {
+            let weak = weak.clone();
+            Box::pin(async move {
+                let Some(resource) = weak.upgrade() else {
+                    return;
+                };
+                let state = ice_connection_state_atom(state);
+                send(pid, move |env| {
+                    (atoms::ice_connection_state_change(), resource, state).encode(env)
+                });
+            })
+        }));
+    }
+
+    {
+        let weak = weak.clone();
+        pc.on_track(Box::new(move |track, _receiver, _transceiver| {
+            let weak = weak.clone();
+            Box::pin(async move {
+                let Some(resource) = weak.upgrade() else {
+                    return;
+                };
+                let kind = track_kind_atom(track.kind());
+                send(pid, move |env| {
+                    (atoms::track(), resource, track.id(), kind).encode(env)
+                });
+            })
+        }));
+    }
+
+    {
+        let weak = weak.clone();
+        pc.on_data_channel(Box::new(move |channel| {
+            let weak = weak.clone();
+            Box::pin(async move {
+                let Some(resource) = weak.upgrade() else {
+                    return;
+                };
+                let label = channel.label().to_string();
+                let id = data_channel::register(resource.clone(), Some(pid), channel);
+
+                send(pid, move |env| {
+                    (atoms::data_channel(), resource, id, label).encode(env)
+                });
+            })
+        }));
+    }
+
+    {
+        let weak = weak.clone();
+        pc.on_negotiation_needed(Box::new(move || {
+            let weak = weak.clone();
+            Box::pin(async move {
+                let Some(resource) = weak.upgrade() else {
+                    return;
+                };
+                send(pid, move |env| (atoms::negotiation_needed(), resource).encode(env));
+            })
+        }));
+    }
+}
+
+/// Maps a track's media kind to the same `:audio` / `:video` atom vocabulary
+/// used elsewhere, rather than the `to_string()` output of `RTPCodecType`.
+fn track_kind_atom(kind: RTPCodecType) -> rustler::Atom {
+    match kind {
+        RTPCodecType::Audio => atoms::audio(),
+        RTPCodecType::Video => atoms::video(),
+        RTPCodecType::Unspecified => atoms::unspecified(),
+    }
+}
+
+fn send<F>(pid: LocalPid, closure: F)
+where
+    F: for<'a> FnOnce(Env<'a>) -> rustler::Term<'a> + 'static,
+{
+    let mut owned_env = OwnedEnv::new();
+    let _ = owned_env.send_and_clear(&pid, closure);
+}
@@ -1,25 +1,38 @@
-use rustler::{Env, Term};
-
 mod atoms;
 mod config;
+mod data_channel;
+mod events;
+mod sdp;
 mod state;
-
-fn on_load(env: Env, _info: Term) -> bool {
-    rustler::resource!(state::Ref, env);
-    true
-}
+#[cfg(test)]
+mod test_support;
 
 rustler::init!(
     "Elixir.Specter.Native",
     [
+        data_channel::create_data_channel,
+        data_channel::data_channel_close,
+        data_channel::data_channel_send,
+        data_channel::data_channel_send_text,
+        sdp::add_ice_candidate,
+        sdp::create_answer,
+        sdp::create_offer,
+        sdp::set_local_description,
+        sdp::set_remote_description,
+        state::close,
+        state::connection_state,
         state::get_config,
+        state::get_stats,
+        state::ice_connection_state,
+        state::ice_gathering_state,
         state::init,
         state::media_engine_exists,
         state::new_api,
         state::new_media_engine,
         state::new_peer_connection,
         state::new_registry,
+        state::register_callbacks,
         state::registry_exists,
-    ],
-    load = on_load
+        state::signaling_state,
+    ]
 );
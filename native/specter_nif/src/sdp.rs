@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use rustler::types::map::map_new;
+use rustler::{Encoder, Env, NifResult, ResourceArc, Term};
+use tokio::runtime::Runtime;
+use webrtc::ice_transport::ice_candidate::RTCIceCandidateInit;
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
+use webrtc::peer_connection::RTCPeerConnection;
+
+use crate::atoms;
+use crate::state::Ref;
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn create_offer<'a>(env: Env<'a>, resource: ResourceArc<Ref>) -> NifResult<Term<'a>> {
+    let (pc, runtime) = peer_connection(&resource)?;
+
+    let offer = runtime
+        .block_on(async move { pc.create_offer(None).await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    encode_description(env, atoms::offer(), &offer)
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn create_answer<'a>(env: Env<'a>, resource: ResourceArc<Ref>) -> NifResult<Term<'a>> {
+    let (pc, runtime) = peer_connection(&resource)?;
+
+    let answer = runtime
+        .block_on(async move { pc.create_answer(None).await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    encode_description(env, atoms::answer(), &answer)
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn set_local_description(
+    resource: ResourceArc<Ref>,
+    sdp_type: rustler::Atom,
+    sdp: String,
+) -> NifResult<rustler::Atom> {
+    let (pc, runtime) = peer_connection(&resource)?;
+    let description = parse_description(sdp_type, sdp)?;
+
+    runtime
+        .block_on(async move { pc.set_local_description(description).await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn set_remote_description(
+    resource: ResourceArc<Ref>,
+    sdp_type: rustler::Atom,
+    sdp: String,
+) -> NifResult<rustler::Atom> {
+    let (pc, runtime) = peer_connection(&resource)?;
+    let description = parse_description(sdp_type, sdp)?;
+
+    runtime
+        .block_on(async move { pc.set_remote_description(description).await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn add_ice_candidate(resource: ResourceArc<Ref>, candidate_json: String) -> NifResult<rustler::Atom> {
+    let (pc, runtime) = peer_connection(&resource)?;
+
+    let candidate: RTCIceCandidateInit = serde_json::from_str(&candidate_json)
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::invalid_config())))?;
+
+    runtime
+        .block_on(async move { pc.add_ice_candidate(candidate).await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    Ok(atoms::ok())
+}
+
+fn peer_connection(resource: &ResourceArc<Ref>) -> NifResult<(Arc<RTCPeerConnection>, Arc<Runtime>)> {
+    let state = resource.0.lock().expect("specter: state lock poisoned");
+    let pc = state
+        .peer_connection
+        .clone()
+        .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))?;
+
+    Ok((pc, state.runtime.clone()))
+}
+
+fn encode_description<'a>(
+    env: Env<'a>,
+    type_atom: rustler::Atom,
+    description: &RTCSessionDescription,
+) -> NifResult<Term<'a>> {
+    let map = map_new(env)
+        .map_put(atoms::r#type().encode(env), type_atom.encode(env))?
+        .map_put(atoms::sdp().encode(env), description.sdp.encode(env))?;
+
+    Ok(map)
+}
+
+/// Accepts the same `:offer` / `:answer` atom that [`create_offer`] and
+/// [`create_answer`] hand back in their `%{type: ..., sdp: ...}` map, so the
+/// negotiation loop can feed one straight into `set_local_description/3` or
+/// `set_remote_description/3` without an undocumented atom-to-string
+/// conversion on the Elixir side.
+fn parse_description(sdp_type: rustler::Atom, sdp: String) -> NifResult<RTCSessionDescription> {
+    let description = if sdp_type == atoms::offer() {
+        RTCSessionDescription::offer(sdp)
+    } else if sdp_type == atoms::answer() {
+        RTCSessionDescription::answer(sdp)
+    } else {
+        return Err(rustler::Error::Term(Box::new(atoms::invalid_config())));
+    };
+
+    description.map_err(|_| rustler::Error::Term(Box::new(atoms::invalid_config())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::new_peer_connection;
+    use webrtc::peer_connection::signaling_state::RTCSignalingState;
+
+    #[tokio::test]
+    async fn offer_answer_round_trips_through_atom_typed_set_description() {
+        let offerer = new_peer_connection().await;
+        let answerer = new_peer_connection().await;
+
+        // A connection with nothing on it has nothing to negotiate.
+        offerer
+            .create_data_channel("probe", None)
+            .await
+            .expect("create_data_channel");
+
+        let offer = offerer.create_offer(None).await.expect("create_offer");
+        let offer_sdp = offer.sdp.clone();
+        offerer
+            .set_local_description(offer)
+            .await
+            .expect("offerer set_local_description");
+
+        // This is exactly what an Elixir caller does: feed the `:offer`
+        // atom `create_offer` reported straight back into
+        // `set_remote_description`, with no atom-to-string conversion in
+        // between.
+        let remote_offer = parse_description(atoms::offer(), offer_sdp).expect("parse offer");
+        answerer
+            .set_remote_description(remote_offer)
+            .await
+            .expect("answerer set_remote_description");
+
+        let answer = answerer.create_answer(None).await.expect("create_answer");
+        let answer_sdp = answer.sdp.clone();
+        answerer
+            .set_local_description(answer)
+            .await
+            .expect("answerer set_local_description");
+
+        let remote_answer = parse_description(atoms::answer(), answer_sdp).expect("parse answer");
+        offerer
+            .set_remote_description(remote_answer)
+            .await
+            .expect("offerer set_remote_description");
+
+        assert_eq!(offerer.signaling_state(), RTCSignalingState::Stable);
+        assert_eq!(answerer.signaling_state(), RTCSignalingState::Stable);
+
+        let _ = offerer.close().await;
+        let _ = answerer.close().await;
+    }
+
+    #[test]
+    fn parse_description_rejects_an_atom_that_is_not_offer_or_answer() {
+        let err = parse_description(atoms::ok(), "v=0".to_string()).unwrap_err();
+        assert!(matches!(err, rustler::Error::Term(_)));
+    }
+}
@@ -0,0 +1,370 @@
+use std::sync::{Arc, Mutex};
+
+use rustler::types::map::map_new;
+use rustler::{Encoder, Env, LocalPid, MonitorInfo, NifResult, Resource, ResourceArc, Term};
+use tokio::runtime::Runtime;
+use webrtc::api::interceptor_registry::register_default_interceptors;
+use webrtc::api::media_engine::MediaEngine;
+use webrtc::api::{APIBuilder, API};
+use webrtc::ice_transport::ice_connection_state::RTCIceConnectionState;
+use webrtc::ice_transport::ice_gathering_state::RTCIceGatheringState;
+use webrtc::interceptor::registry::Registry;
+use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
+use webrtc::peer_connection::signaling_state::RTCSignalingState;
+use webrtc::peer_connection::RTCPeerConnection;
+
+use crate::atoms;
+use crate::config::Config;
+use crate::data_channel::DataChannels;
+use crate::events;
+
+/// Native state backing a `Specter.Native` resource handle.
+///
+/// Everything here lives behind a single [`Mutex`] because the NIFs that
+/// operate on a `Ref` are called from arbitrary BEAM scheduler threads, and
+/// the `webrtc` types themselves are not safe to mutate concurrently.
+pub struct RefState {
+    pub(crate) runtime: Arc<Runtime>,
+    pub(crate) config: Config,
+    pub(crate) media_engine: Option<Arc<Mutex<MediaEngine>>>,
+    pub(crate) registry: Option<Registry>,
+    pub(crate) api: Option<Arc<API>>,
+    pub(crate) peer_connection: Option<Arc<RTCPeerConnection>>,
+    pub(crate) pid: Option<LocalPid>,
+    pub(crate) data_channels: DataChannels,
+    closed: bool,
+}
+
+pub struct Ref(pub(crate) Mutex<RefState>);
+
+#[rustler::resource_impl]
+impl Resource for Ref {
+    /// Called by the BEAM when the process that created this resource (and
+    /// which `init` set it to monitor) dies without having explicitly closed it. This is the
+    /// native-side half of "supervision trees clean up after themselves":
+    /// a crashed LiveView or GenServer must not leak an open
+    /// `RTCPeerConnection` and its ICE/DTLS transports.
+    ///
+    /// `take_peer_connection_for_close` is idempotent, so this is safe to
+    /// run even if the caller already closed the connection explicitly
+    /// before dying.
+    ///
+    /// `MonitorInfo` is the down-callback parameter type as of the `rustler`
+    /// version pinned in `Cargo.toml`; bump both together if upgrading.
+    fn down(&self, _env: Env<'_>, _pid: LocalPid, _monitor_info: MonitorInfo) {
+        let (pc, runtime) = {
+            let mut state = self.0.lock().expect("specter: state lock poisoned");
+            let pc = take_peer_connection_for_close(&mut state);
+            (pc, state.runtime.clone())
+        };
+
+        let Some(pc) = pc else {
+            return;
+        };
+
+        // Best-effort: the owning process is already gone, so there is
+        // nobody left to report a close error to.
+        runtime.block_on(async move {
+            let _ = pc.close().await;
+        });
+    }
+}
+
+/// Mark `state` closed and hand back the `RTCPeerConnection` (if any and if
+/// not already closed) for the caller to `.close()` itself, once the
+/// `Mutex` guard has been released. `RTCPeerConnection::close` awaits
+/// DTLS/SCTP teardown, and doing that while still holding the lock would
+/// block every other NIF operating on this `Ref` for as long as it takes.
+fn take_peer_connection_for_close(state: &mut RefState) -> Option<Arc<RTCPeerConnection>> {
+    if state.closed {
+        return None;
+    }
+    state.closed = true;
+
+    let pc = state.peer_connection.take();
+
+    state.api = None;
+    state.registry = None;
+    state.media_engine = None;
+    state.data_channels = DataChannels::default();
+
+    pc
+}
+
+#[rustler::nif]
+pub fn init(env: Env, config: Config) -> NifResult<ResourceArc<Ref>> {
+    let runtime = Runtime::new().map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    let resource = ResourceArc::new(Ref(Mutex::new(RefState {
+        runtime: Arc::new(runtime),
+        config,
+        media_engine: None,
+        registry: None,
+        api: None,
+        peer_connection: None,
+        pid: None,
+        data_channels: DataChannels::default(),
+        closed: false,
+    })));
+
+    env.monitor(&resource, &env.pid());
+
+    Ok(resource)
+}
+
+#[rustler::nif]
+pub fn get_config(resource: ResourceArc<Ref>) -> NifResult<Config> {
+    let state = resource.0.lock().expect("specter: state lock poisoned");
+    Ok(state.config.clone())
+}
+
+#[rustler::nif]
+pub fn new_media_engine(resource: ResourceArc<Ref>) -> NifResult<rustler::Atom> {
+    let mut state = resource.0.lock().expect("specter: state lock poisoned");
+    state.media_engine = Some(Arc::new(Mutex::new(MediaEngine::default())));
+    Ok(atoms::ok())
+}
+
+#[rustler::nif]
+pub fn media_engine_exists(resource: ResourceArc<Ref>) -> bool {
+    let state = resource.0.lock().expect("specter: state lock poisoned");
+    state.media_engine.is_some()
+}
+
+#[rustler::nif]
+pub fn new_registry(resource: ResourceArc<Ref>) -> NifResult<rustler::Atom> {
+    let mut state = resource.0.lock().expect("specter: state lock poisoned");
+
+    let media_engine = state
+        .media_engine
+        .clone()
+        .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))?;
+
+    let mut registry = Registry::new();
+    {
+        let mut engine = media_engine.lock().expect("specter: media engine lock poisoned");
+        registry = register_default_interceptors(registry, &mut engine)
+            .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+    }
+
+    state.registry = Some(registry);
+    Ok(atoms::ok())
+}
+
+#[rustler::nif]
+pub fn registry_exists(resource: ResourceArc<Ref>) -> bool {
+    let state = resource.0.lock().expect("specter: state lock poisoned");
+    state.registry.is_some()
+}
+
+#[rustler::nif]
+pub fn new_api(resource: ResourceArc<Ref>) -> NifResult<rustler::Atom> {
+    let mut state = resource.0.lock().expect("specter: state lock poisoned");
+
+    let media_engine = state
+        .media_engine
+        .take()
+        .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))?;
+    let registry = state
+        .registry
+        .take()
+        .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))?;
+
+    let media_engine = Arc::try_unwrap(media_engine)
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?
+        .into_inner()
+        .expect("specter: media engine lock poisoned");
+
+    let api = APIBuilder::new()
+        .with_media_engine(media_engine)
+        .with_interceptor_registry(registry)
+        .build();
+
+    state.api = Some(Arc::new(api));
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn new_peer_connection(resource: ResourceArc<Ref>) -> NifResult<rustler::Atom> {
+    let (api, runtime, rtc_config) = {
+        let state = resource.0.lock().expect("specter: state lock poisoned");
+        let api = state
+            .api
+            .clone()
+            .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))?;
+        (api, state.runtime.clone(), state.config.clone().into_rtc_configuration())
+    };
+
+    let peer_connection = runtime
+        .block_on(async move { api.new_peer_connection(rtc_config).await })
+        .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+
+    let mut state = resource.0.lock().expect("specter: state lock poisoned");
+    state.peer_connection = Some(Arc::new(peer_connection));
+    Ok(atoms::ok())
+}
+
+/// Explicitly tear down the peer connection (and any data channels on it)
+/// rather than waiting for the owning process to die. Safe to call more
+/// than once, and safe to skip entirely: [`Resource::down`] runs the same
+/// teardown if the caller never gets here.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn close(resource: ResourceArc<Ref>) -> NifResult<rustler::Atom> {
+    let (pc, runtime) = {
+        let mut state = resource.0.lock().expect("specter: state lock poisoned");
+        let pc = take_peer_connection_for_close(&mut state);
+        (pc, state.runtime.clone())
+    };
+
+    if let Some(pc) = pc {
+        runtime
+            .block_on(async move { pc.close().await })
+            .map_err(|_| rustler::Error::Term(Box::new(atoms::error())))?;
+    }
+
+    Ok(atoms::ok())
+}
+
+/// Subscribe `pid` to this connection's asynchronous events: ICE
+/// candidates, ICE connection state changes, incoming tracks and data
+/// channels, and renegotiation requests. Each event arrives at `pid` as a
+/// tagged message carrying this `Ref` so a caller juggling multiple
+/// connections can tell them apart; see [`events::register`].
+#[rustler::nif]
+pub fn register_callbacks(resource: ResourceArc<Ref>, pid: LocalPid) -> NifResult<rustler::Atom> {
+    let pc = {
+        let mut state = resource.0.lock().expect("specter: state lock poisoned");
+        state.pid = Some(pid);
+        state
+            .peer_connection
+            .clone()
+            .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))?
+    };
+
+    events::register(resource.clone(), &pc, pid);
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif]
+pub fn connection_state(resource: ResourceArc<Ref>) -> NifResult<rustler::Atom> {
+    let pc = peer_connection_handle(&resource)?;
+    Ok(match pc.connection_state() {
+        RTCPeerConnectionState::New => atoms::new(),
+        RTCPeerConnectionState::Connecting => atoms::connecting(),
+        RTCPeerConnectionState::Connected => atoms::connected(),
+        RTCPeerConnectionState::Disconnected => atoms::disconnected(),
+        RTCPeerConnectionState::Failed => atoms::failed(),
+        RTCPeerConnectionState::Closed => atoms::closed(),
+        RTCPeerConnectionState::Unspecified => atoms::unspecified(),
+    })
+}
+
+#[rustler::nif]
+pub fn ice_connection_state(resource: ResourceArc<Ref>) -> NifResult<rustler::Atom> {
+    let pc = peer_connection_handle(&resource)?;
+    Ok(ice_connection_state_atom(pc.ice_connection_state()))
+}
+
+/// Shared with [`crate::events::register`], so the `ice_connection_state`
+/// getter and the `:ice_connection_state_change` event agree on
+/// representation: both hand Elixir the same atom for the same
+/// `RTCIceConnectionState`, rather than a getter atom and an event string.
+pub(crate) fn ice_connection_state_atom(state: RTCIceConnectionState) -> rustler::Atom {
+    match state {
+        RTCIceConnectionState::New => atoms::new(),
+        RTCIceConnectionState::Checking => atoms::checking(),
+        RTCIceConnectionState::Connected => atoms::connected(),
+        RTCIceConnectionState::Completed => atoms::completed(),
+        RTCIceConnectionState::Disconnected => atoms::disconnected(),
+        RTCIceConnectionState::Failed => atoms::failed(),
+        RTCIceConnectionState::Closed => atoms::closed(),
+        RTCIceConnectionState::Unspecified => atoms::unspecified(),
+    }
+}
+
+#[rustler::nif]
+pub fn ice_gathering_state(resource: ResourceArc<Ref>) -> NifResult<rustler::Atom> {
+    let pc = peer_connection_handle(&resource)?;
+    Ok(match pc.ice_gathering_state() {
+        RTCIceGatheringState::New => atoms::new(),
+        RTCIceGatheringState::Gathering => atoms::gathering(),
+        RTCIceGatheringState::Complete => atoms::complete(),
+        RTCIceGatheringState::Unspecified => atoms::unspecified(),
+    })
+}
+
+#[rustler::nif]
+pub fn signaling_state(resource: ResourceArc<Ref>) -> NifResult<rustler::Atom> {
+    let pc = peer_connection_handle(&resource)?;
+    Ok(match pc.signaling_state() {
+        RTCSignalingState::Stable => atoms::stable(),
+        RTCSignalingState::HaveLocalOffer => atoms::have_local_offer(),
+        RTCSignalingState::HaveRemoteOffer => atoms::have_remote_offer(),
+        RTCSignalingState::HaveLocalPranswer => atoms::have_local_pranswer(),
+        RTCSignalingState::HaveRemotePranswer => atoms::have_remote_pranswer(),
+        RTCSignalingState::Closed => atoms::closed(),
+        RTCSignalingState::Unspecified => atoms::unspecified(),
+    })
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn get_stats<'a>(env: Env<'a>, resource: ResourceArc<Ref>) -> NifResult<Term<'a>> {
+    let (pc, runtime) = {
+        let state = resource.0.lock().expect("specter: state lock poisoned");
+        let pc = state
+            .peer_connection
+            .clone()
+            .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))?;
+        (pc, state.runtime.clone())
+    };
+
+    let report = runtime.block_on(async move { pc.get_stats().await });
+
+    let mut map = map_new(env);
+    for (id, stat) in report.reports.iter() {
+        let Ok(value) = serde_json::to_value(stat) else {
+            continue;
+        };
+        map = map.map_put(id.as_str().encode(env), json_to_term(env, &value))?;
+    }
+
+    Ok(map)
+}
+
+/// Decode a `serde_json::Value` into the equivalent Elixir term, so
+/// `get_stats` hands back a real (if loosely typed) nested map rather than
+/// a JSON string every caller would have to `Jason.decode!/1` themselves.
+fn json_to_term<'a>(env: Env<'a>, value: &serde_json::Value) -> Term<'a> {
+    match value {
+        serde_json::Value::Null => atoms::nil().encode(env),
+        serde_json::Value::Bool(b) => b.encode(env),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => i.encode(env),
+            None => n.as_f64().unwrap_or_default().encode(env),
+        },
+        serde_json::Value::String(s) => s.encode(env),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| json_to_term(env, item))
+            .collect::<Vec<_>>()
+            .encode(env),
+        serde_json::Value::Object(fields) => {
+            let mut map = map_new(env);
+            for (key, value) in fields {
+                map = map
+                    .map_put(key.encode(env), json_to_term(env, value))
+                    .expect("specter: building get_stats map");
+            }
+            map
+        }
+    }
+}
+
+fn peer_connection_handle(resource: &ResourceArc<Ref>) -> NifResult<Arc<RTCPeerConnection>> {
+    let state = resource.0.lock().expect("specter: state lock poisoned");
+    state
+        .peer_connection
+        .clone()
+        .ok_or_else(|| rustler::Error::Term(Box::new(atoms::not_found())))
+}
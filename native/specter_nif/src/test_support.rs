@@ -0,0 +1,48 @@
+//! Shared helpers for the integration-style tests in `sdp.rs` and
+//! `data_channel.rs`. These build real `RTCPeerConnection`s and drive a full
+//! (non-trickled) offer/answer exchange between them straight through
+//! `webrtc-rs` — none of this touches `rustler::Env`, so unlike the NIFs,
+//! which need a live BEAM, it runs under plain `cargo test`.
+#![cfg(test)]
+
+use webrtc::api::interceptor_registry::register_default_interceptors;
+use webrtc::api::media_engine::MediaEngine;
+use webrtc::api::APIBuilder;
+use webrtc::interceptor::registry::Registry;
+use webrtc::peer_connection::configuration::RTCConfiguration;
+use webrtc::peer_connection::RTCPeerConnection;
+
+pub async fn new_peer_connection() -> RTCPeerConnection {
+    let mut media_engine = MediaEngine::default();
+    let mut registry = Registry::new();
+    registry = register_default_interceptors(registry, &mut media_engine).expect("register interceptors");
+
+    let api = APIBuilder::new()
+        .with_media_engine(media_engine)
+        .with_interceptor_registry(registry)
+        .build();
+
+    api.new_peer_connection(RTCConfiguration::default())
+        .await
+        .expect("new_peer_connection")
+}
+
+/// Full (non-trickled) offer/answer exchange: wait for ICE gathering to
+/// finish on each side before handing its description to the other peer,
+/// so neither side needs `add_ice_candidate`.
+pub async fn negotiate(offerer: &RTCPeerConnection, answerer: &RTCPeerConnection) {
+    let offer = offerer.create_offer(None).await.expect("create_offer");
+    let mut gather_complete = offerer.gathering_complete_promise().await;
+    offerer.set_local_description(offer).await.expect("offerer set_local_description");
+    let _ = gather_complete.recv().await;
+    let offer = offerer.local_description().await.expect("offerer local_description");
+
+    answerer.set_remote_description(offer).await.expect("answerer set_remote_description");
+    let answer = answerer.create_answer(None).await.expect("create_answer");
+    let mut gather_complete = answerer.gathering_complete_promise().await;
+    answerer.set_local_description(answer).await.expect("answerer set_local_description");
+    let _ = gather_complete.recv().await;
+    let answer = answerer.local_description().await.expect("answerer local_description");
+
+    offerer.set_remote_description(answer).await.expect("offerer set_remote_description");
+}